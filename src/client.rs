@@ -1,11 +1,12 @@
 use tikv_client::TransactionClient;
 use serde_json::Value as JsonValue;
 use crate::errors::OsunbitDBError;
+use crate::models::{BulkWriteResult, ScanOptions, ScanResult, WriteModel};
 use crate::transaction::TransactionHandle;
 
 #[derive(Clone)]
 pub struct OsunbitDB {
-    client: TransactionClient, 
+    client: TransactionClient,
 }
 
 impl OsunbitDB {
@@ -19,6 +20,55 @@ impl OsunbitDB {
         Ok(TransactionHandle { tx })
     }
 
+    /// Register a dot-notation field to be indexed for `collection`, durably
+    /// (stored as a `meta:index:<collection>:<field>` key, not in-process
+    /// state). From this point on, `add`/`update`/`delete` maintain
+    /// `idx:<collection>:<field>:<encoded_value>:<docid>` entries so
+    /// `query`/`query_range` can look documents up by value. Existing
+    /// documents written before the field was indexed are not backfilled.
+    pub async fn create_index(&self, collection: &str, field: &str) -> Result<(), OsunbitDBError> {
+        let mut tx = self.transaction().await?;
+        tx.create_index(collection, field).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Look up documents in `collection` whose `field` equals `value`, via
+    /// the index registered with `create_index`. Paginates like `scan_range`:
+    /// pass the previous call's `next_cursor` back in as `cursor` to resume.
+    pub async fn query(
+        &self,
+        collection: &str,
+        field: &str,
+        value: &JsonValue,
+        limit: u32,
+        cursor: &str,
+    ) -> Result<ScanResult, OsunbitDBError> {
+        let mut tx = self.transaction().await?;
+        let result = tx.query(collection, field, value, limit, cursor).await?;
+        tx.rollback().await?;
+        Ok(result)
+    }
+
+    /// Look up documents in `collection` whose `field` falls within
+    /// `[lo, hi]`, via the index registered with `create_index`. Paginates
+    /// like `scan_range`: pass the previous call's `next_cursor` back in as
+    /// `cursor` to resume.
+    pub async fn query_range(
+        &self,
+        collection: &str,
+        field: &str,
+        lo: &JsonValue,
+        hi: &JsonValue,
+        limit: u32,
+        cursor: &str,
+    ) -> Result<ScanResult, OsunbitDBError> {
+        let mut tx = self.transaction().await?;
+        let result = tx.query_range(collection, field, lo, hi, limit, cursor).await?;
+        tx.rollback().await?;
+        Ok(result)
+    }
+
     pub async fn add(&self, collection: &str, id: &str, value: &JsonValue) -> Result<(), OsunbitDBError> {
         let mut tx = self.transaction().await?;
         tx.add(collection, id, value).await?;
@@ -47,10 +97,115 @@ impl OsunbitDB {
         Ok(())
     }
 
-    pub async fn scan(&self, collection: &str, limit: u32) -> Result<JsonValue, OsunbitDBError> {
+    /// Fetch a document alongside a causality token for safe read-modify-write.
+    pub async fn get_with_token(&self, collection: &str, id: &str) -> Result<Option<(JsonValue, String)>, OsunbitDBError> {
+        let mut tx = self.transaction().await?;
+        let result = tx.get_with_token(collection, id).await?;
+        tx.rollback().await?;
+        Ok(result)
+    }
+
+    /// Compare-and-set update: applies `fields` only if `expected_token` still matches.
+    pub async fn update_if(
+        &self,
+        collection: &str,
+        id: &str,
+        fields: &JsonValue,
+        expected_token: &str,
+    ) -> Result<(), OsunbitDBError> {
+        let mut tx = self.transaction().await?;
+        // Roll back before returning a conflict: dropping an active transaction panics.
+        if let Err(e) = tx.update_if(collection, id, fields, expected_token).await {
+            tx.rollback().await?;
+            return Err(e);
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Scan a sub-range of `collection`'s doc ids; see [`ScanOptions`] for
+    /// forward/backward direction and bound semantics.
+    pub async fn scan_range(&self, collection: &str, opts: &ScanOptions) -> Result<ScanResult, OsunbitDBError> {
+        let mut tx = self.transaction().await?;
+        let result = tx.scan_range(collection, opts).await?;
+        tx.rollback().await?;
+        Ok(result)
+    }
+
+    pub async fn batch_add(&self, collection: &str, items_json: &JsonValue) -> Result<(), OsunbitDBError> {
+        let mut tx = self.transaction().await?;
+        tx.batch_add(collection, items_json).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn batch_get(&self, collection: &str, ids_json: &JsonValue) -> Result<JsonValue, OsunbitDBError> {
         let mut tx = self.transaction().await?;
-        let result = tx.scan(collection, limit).await?;
+        let result = tx.batch_get(collection, ids_json).await?;
         tx.rollback().await?;
         Ok(result)
     }
+
+    pub async fn batch_delete(&self, collection: &str, ids_json: &JsonValue) -> Result<(), OsunbitDBError> {
+        let mut tx = self.transaction().await?;
+        tx.batch_delete(collection, ids_json).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Run a heterogeneous set of inserts/updates/deletes as one atomic unit
+    /// of work, e.g. debiting one account, crediting another, and writing an
+    /// audit record in a single commit.
+    pub async fn bulk_write(&self, models: Vec<WriteModel>) -> Result<BulkWriteResult, OsunbitDBError> {
+        let mut tx = self.transaction().await?;
+        let result = tx.bulk_write(models).await?;
+        tx.commit().await?;
+        Ok(result)
+    }
+
+    /// Reap up to `limit` documents that are already past their `expiryAt`,
+    /// returning the number reaped and a cursor to resume from if more are due.
+    pub async fn sweep_expired(
+        &self,
+        now: &str,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<(u32, Option<String>), OsunbitDBError> {
+        let mut tx = self.transaction().await?;
+        let result = tx.sweep_expired(now, limit, cursor).await?;
+        tx.commit().await?;
+        Ok(result)
+    }
+
+    /// Spawn a background loop that drains the `expire:` keyspace `limit` at a
+    /// time until dry, then sleeps `interval`. `now_fn` supplies the current RFC-3339 timestamp.
+    pub fn spawn_ttl_reaper<F>(
+        &self,
+        now_fn: F,
+        interval: std::time::Duration,
+        limit: u32,
+    ) -> tokio::task::JoinHandle<()>
+    where
+        F: Fn() -> String + Send + Sync + 'static,
+    {
+        let db = self.clone();
+        tokio::spawn(async move {
+            loop {
+                let now = now_fn();
+                let mut cursor: Option<String> = None;
+                loop {
+                    match db.sweep_expired(&now, limit, cursor.as_deref()).await {
+                        Ok((_reaped, next_cursor)) => {
+                            if next_cursor.is_none() {
+                                break;
+                            }
+                            cursor = next_cursor;
+                        }
+                        Err(_) => break,
+                    }
+                }
+                tokio::time::sleep(interval).await;
+            }
+        })
+    }
 }
@@ -19,4 +19,7 @@ pub enum OsunbitDBError {
 
     #[error("Invalid update: {0}")]
     InvalidUpdate(String),
+
+    #[error("Conflict: {0}")]
+    ConflictToken(String),
 }
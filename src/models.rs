@@ -0,0 +1,59 @@
+// src/models.rs
+use serde_json::{Map, Value as JsonValue};
+
+/// A single write for [`TransactionHandle::bulk_write`](crate::TransactionHandle::bulk_write);
+/// each variant carries its own `collection` so one call can touch several atomically.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WriteModel {
+    InsertOne {
+        collection: String,
+        id: String,
+        document: JsonValue,
+    },
+    UpdateOne {
+        collection: String,
+        id: String,
+        fields: JsonValue,
+    },
+    DeleteOne {
+        collection: String,
+        id: String,
+    },
+}
+
+/// Per-category counts describing what a `bulk_write` actually did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BulkWriteResult {
+    pub inserted: u32,
+    pub matched: u32,
+    pub modified: u32,
+    pub deleted: u32,
+}
+
+/// Bounds and direction for [`TransactionHandle::scan_range`](crate::TransactionHandle::scan_range).
+/// `start`/`end` are doc ids, not raw keys; `None` scans to that edge of the collection.
+///
+/// To fetch the next page, feed the previous [`ScanResult::next_cursor`] back
+/// in as `start` with `start_exclusive` set when `reverse` is `false`, or as
+/// `end` with `end_exclusive` set when `reverse` is `true` — the cursor is the last id the scan produced,
+/// and the next page resumes strictly beyond it in whichever direction the
+/// scan is moving, not necessarily the `start` edge.
+#[derive(Debug, Clone, Default)]
+pub struct ScanOptions {
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub reverse: bool,
+    pub start_exclusive: bool,
+    pub end_exclusive: bool,
+    pub limit: u32,
+}
+
+/// A page of documents returned by `scan_range`, with an opaque cursor for
+/// fetching the next page in the same direction. The cursor is direction-aware:
+/// see [`ScanOptions`] for which bound to plug it into.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScanResult {
+    pub items: Map<String, JsonValue>,
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
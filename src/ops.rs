@@ -1,5 +1,6 @@
 use serde_json::json;
 use serde_json::Value as Json;
+use crate::errors::OsunbitDBError;
 
 pub fn increment(amount: i64) -> Json {
     json!({ "__op": "inc", "amount": amount })
@@ -12,3 +13,37 @@ pub fn remove() -> Json {
 pub fn array_union(values: Json) -> Json {
     json!({ "__op": "array_union", "values": values })
 }
+
+pub fn array_remove(values: Json) -> Json {
+    json!({ "__op": "array_remove", "values": values })
+}
+
+/// Errors with `InvalidUpdate` for NaN/infinite `factor`, which `json!` would otherwise turn into `null`.
+pub fn multiply(factor: f64) -> Result<Json, OsunbitDBError> {
+    if !factor.is_finite() {
+        return Err(OsunbitDBError::InvalidUpdate(format!("multiply factor must be finite, got {}", factor)));
+    }
+    Ok(json!({ "__op": "mul", "amount": factor }))
+}
+
+/// Errors with `InvalidUpdate` for NaN/infinite `value`, which `json!` would otherwise turn into `null`.
+pub fn minimum(value: f64) -> Result<Json, OsunbitDBError> {
+    if !value.is_finite() {
+        return Err(OsunbitDBError::InvalidUpdate(format!("minimum value must be finite, got {}", value)));
+    }
+    Ok(json!({ "__op": "min", "amount": value }))
+}
+
+/// Errors with `InvalidUpdate` for NaN/infinite `value`, which `json!` would otherwise turn into `null`.
+pub fn maximum(value: f64) -> Result<Json, OsunbitDBError> {
+    if !value.is_finite() {
+        return Err(OsunbitDBError::InvalidUpdate(format!("maximum value must be finite, got {}", value)));
+    }
+    Ok(json!({ "__op": "max", "amount": value }))
+}
+
+/// Only applied when the field is currently absent, e.g. to stamp a
+/// `createdAt` on insert without clobbering it on every later update.
+pub fn set_on_insert(value: impl Into<Json>) -> Json {
+    json!({ "__op": "set_on_insert", "value": value.into() })
+}
@@ -1,7 +1,8 @@
 use tikv_client::{Transaction, Key, Value, KvPair, BoundRange};
 use serde_json::{Value as JsonValue, Map};
 use crate::errors::OsunbitDBError;
-use crate::utils::{set_deep, get_deep, remove_deep};
+use crate::models::{BulkWriteResult, ScanOptions, ScanResult, WriteModel};
+use crate::utils::{set_deep, get_deep, remove_deep, encode_index_value};
 use serde_json::json;
 
 
@@ -9,15 +10,206 @@ pub struct TransactionHandle {
     pub(crate) tx: Transaction,
 }
 
+/// Combines a field's current value with an operand for `inc`/`mul`/`min`/`max`,
+/// staying in `i64` unless either side is a float. `default_is_operand` picks
+/// what a missing field counts as: `0` for `inc`/`mul`, the operand itself for `min`/`max`.
+fn numeric_op(
+    current: Option<&JsonValue>,
+    operand: &JsonValue,
+    default_is_operand: bool,
+    combine_f64: impl Fn(f64, f64) -> f64,
+    combine_i64: impl Fn(i64, i64) -> i64,
+) -> JsonValue {
+    let uses_float = current.map(|v| v.is_f64()).unwrap_or(false) || operand.is_f64();
+
+    if uses_float {
+        let default = if default_is_operand { operand.as_f64().unwrap_or(0.0) } else { 0.0 };
+        let cur = current.and_then(|v| v.as_f64()).unwrap_or(default);
+        let amt = operand.as_f64().unwrap_or(0.0);
+        json!(combine_f64(cur, amt))
+    } else {
+        let default = if default_is_operand { operand.as_i64().unwrap_or(0) } else { 0 };
+        let cur = current.and_then(|v| v.as_i64()).unwrap_or(default);
+        let amt = operand.as_i64().unwrap_or(0);
+        json!(combine_i64(cur, amt))
+    }
+}
+
+/// `__op` merge logic shared by `update` and `update_if`.
+fn apply_op_fields(obj: &mut Map<String, JsonValue>, fields: &JsonValue) -> Result<(), OsunbitDBError> {
+    if let JsonValue::Object(new_fields) = fields {
+        for (k, v) in new_fields {
+            if let Some(op) = v.get("__op") {
+                match op.as_str().unwrap_or("") {
+                    "inc" => {
+                        let current = get_deep(obj, k).cloned();
+                        let result = numeric_op(current.as_ref(), &v["amount"], false, |a, b| a + b, |a, b| a + b);
+                        set_deep(obj, k, result);
+                    }
+                    "mul" => {
+                        let current = get_deep(obj, k).cloned();
+                        let result = numeric_op(current.as_ref(), &v["amount"], false, |a, b| a * b, |a, b| a * b);
+                        set_deep(obj, k, result);
+                    }
+                    "min" => {
+                        let current = get_deep(obj, k).cloned();
+                        let result = numeric_op(current.as_ref(), &v["amount"], true, |a, b| a.min(b), |a, b| a.min(b));
+                        set_deep(obj, k, result);
+                    }
+                    "max" => {
+                        let current = get_deep(obj, k).cloned();
+                        let result = numeric_op(current.as_ref(), &v["amount"], true, |a, b| a.max(b), |a, b| a.max(b));
+                        set_deep(obj, k, result);
+                    }
+                    "set_on_insert" => {
+                        if get_deep(obj, k).is_none() {
+                            set_deep(obj, k, v["value"].clone());
+                        }
+                    }
+                    "remove" => {
+                        remove_deep(obj, k);
+                    }
+                    "array_union" => {
+                        let new_vals = v["values"].as_array().cloned().unwrap_or_default();
+                        let mut existing = get_deep(obj, k)
+                            .and_then(|val| val.as_array().cloned())
+                            .unwrap_or_default();
+
+                        for nv in new_vals {
+                            if !existing.contains(&nv) {
+                                existing.push(nv);
+                            }
+                        }
+
+                        set_deep(obj, k, JsonValue::Array(existing));
+                    }
+                    "array_remove" => {
+                        let rem_vals = v["values"].as_array().cloned().unwrap_or_default();
+                        let mut existing = get_deep(obj, k)
+                            .and_then(|val| val.as_array().cloned())
+                            .unwrap_or_default();
+
+                        existing.retain(|item| !rem_vals.contains(item));
+
+                        set_deep(obj, k, JsonValue::Array(existing));
+                    }
+                    _ => {
+                        set_deep(obj, k, v.clone());
+                    }
+                }
+            } else {
+                set_deep(obj, k, v.clone());
+            }
+        }
+        Ok(())
+    } else {
+        Err(OsunbitDBError::InvalidUpdate(
+            "update fields must be an object".to_string(),
+        ))
+    }
+}
+
+/// A document's causality token: its `__version` field, or `"0"` if unset.
+fn version_token(doc: &JsonValue) -> String {
+    doc.as_object()
+        .and_then(|obj| obj.get("__version"))
+        .and_then(|v| v.as_i64())
+        .unwrap_or(0)
+        .to_string()
+}
+
+/// Bumps `__version` so an `update` or `update_if` observed through `get_with_token`
+/// invalidates a token taken before it — `update` and `update_if` must both call this, or a
+/// plain `update` landing between a caller's `get_with_token` and `update_if`
+/// would go unnoticed and defeat the CAS guarantee. `add` stores the document as
+/// given and does not bump `__version`.
+fn bump_version(obj: &mut Map<String, JsonValue>) {
+    let next_version = obj.get("__version").and_then(|v| v.as_i64()).unwrap_or(0) + 1;
+    obj.insert("__version".to_string(), json!(next_version));
+}
+
 impl TransactionHandle {
     fn key(base: &str, id: &str) -> Key {
         let key = format!("{}:{}", base, id);
         Key::from(key)
     }
 
+    fn index_key(collection: &str, field: &str, encoded_value: &str, id: &str) -> Key {
+        Key::from(format!("idx:{}:{}:{}:{}", collection, field, encoded_value, id))
+    }
+
+    fn meta_index_key(collection: &str, field: &str) -> Key {
+        Key::from(format!("meta:index:{}:{}", collection, field))
+    }
+
+    /// Register a dot-notation field to be indexed for `collection`, durably (survives restart).
+    pub async fn create_index(&mut self, collection: &str, field: &str) -> Result<(), OsunbitDBError> {
+        self.tx
+            .put(Self::meta_index_key(collection, field), Value::from(field.as_bytes().to_vec()))
+            .await?;
+        Ok(())
+    }
+
+    /// The fields currently registered via `create_index` for `collection`.
+    async fn indexed_fields(&mut self, collection: &str) -> Result<Vec<String>, OsunbitDBError> {
+        let prefix = format!("meta:index:{}:", collection);
+        let start_key: Key = Key::from(prefix.clone());
+        let end_key: Key = Key::from(format!("{}\u{10FFFF}", prefix));
+        let range: BoundRange = (start_key..end_key).into();
+
+        let kvs: Vec<KvPair> = self.tx.scan(range, 1024).await?.collect();
+        let mut fields = Vec::with_capacity(kvs.len());
+        for kv in kvs {
+            let k = String::from_utf8_lossy(kv.key().as_ref().into()).to_string();
+            if let Some(field) = k.strip_prefix(&prefix) {
+                fields.push(field.to_string());
+            }
+        }
+        Ok(fields)
+    }
+
+    /// Brings `idx:` entries in line with a document write: removes the
+    /// entry for any indexed field whose value changed or disappeared, and
+    /// adds the entry for any indexed field present in the new value.
+    async fn reindex(
+        &mut self,
+        collection: &str,
+        id: &str,
+        old: Option<&JsonValue>,
+        new: Option<&JsonValue>,
+    ) -> Result<(), OsunbitDBError> {
+        let fields = self.indexed_fields(collection).await?;
+        if fields.is_empty() {
+            return Ok(());
+        }
+
+        for field in fields {
+            let old_val = old.and_then(|v| v.as_object()).and_then(|o| get_deep(o, &field));
+            let new_val = new.and_then(|v| v.as_object()).and_then(|o| get_deep(o, &field));
+
+            if old_val == new_val {
+                continue;
+            }
+
+            if let Some(v) = old_val {
+                let encoded = encode_index_value(v);
+                self.tx.delete(Self::index_key(collection, &field, &encoded, id)).await?;
+            }
+            if let Some(v) = new_val {
+                let encoded = encode_index_value(v);
+                self.tx
+                    .put(Self::index_key(collection, &field, &encoded, id), Value::from(id.as_bytes().to_vec()))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
     pub async fn add(&mut self, collection: &str, id: &str, value: &JsonValue) -> Result<(), OsunbitDBError> {
+        let old = self.get(collection, id).await?;
         let bytes = serde_json::to_vec(value)?;
         self.tx.put(Self::key(collection, id), Value::from(bytes)).await?;
+        self.reindex(collection, id, old.as_ref(), Some(value)).await?;
         Ok(())
     }
 
@@ -32,10 +224,112 @@ impl TransactionHandle {
     }
 
     pub async fn delete(&mut self, collection: &str, id: &str) -> Result<(), OsunbitDBError> {
+        let old = self.get(collection, id).await?;
         self.tx.delete(Self::key(collection, id)).await?;
+        if old.is_some() {
+            self.reindex(collection, id, old.as_ref(), None).await?;
+        }
         Ok(())
     }
 
+    /// Look up documents in `collection` whose `field` equals `value`, paginating
+    /// the same way `scan_range` does. Requires `create_index(collection, field)`
+    /// to have been called first.
+    pub async fn query(
+        &mut self,
+        collection: &str,
+        field: &str,
+        value: &JsonValue,
+        limit: u32,
+        cursor: &str,
+    ) -> Result<ScanResult, OsunbitDBError> {
+        let encoded = encode_index_value(value);
+        let prefix = format!("idx:{}:{}:{}:", collection, field, encoded);
+
+        // `cursor` is the last doc id seen; resume strictly after it. A doc id has
+        // no delimiter after it in this key, so the true successor (id + 0x00) is
+        // needed — a string-max suffix would wrongly skip ids that extend `cursor`.
+        let start_key: Key = if cursor.is_empty() {
+            Key::from(prefix.clone())
+        } else {
+            Key::from(format!("{}{}\0", prefix, cursor))
+        };
+        let end_key: Key = Key::from(format!("{}\u{10FFFF}", prefix));
+        let range: BoundRange = (start_key..end_key).into();
+
+        self.hydrate_index_scan(collection, &prefix, range, limit, |k, prefix| {
+            k.strip_prefix(prefix).unwrap_or(k).to_string()
+        }).await
+    }
+
+    /// Look up documents in `collection` whose `field` falls within `[lo, hi]`,
+    /// paginating the same way `scan_range` does. Requires
+    /// `create_index(collection, field)` to have been called first.
+    pub async fn query_range(
+        &mut self,
+        collection: &str,
+        field: &str,
+        lo: &JsonValue,
+        hi: &JsonValue,
+        limit: u32,
+        cursor: &str,
+    ) -> Result<ScanResult, OsunbitDBError> {
+        let prefix = format!("idx:{}:{}:", collection, field);
+
+        // `cursor` is the last entry seen as `<encoded_value>:<docid>`; resume strictly
+        // after it, same successor-key reasoning as `query`'s cursor (a doc id has no
+        // delimiter after it in this key).
+        let start_key: Key = if cursor.is_empty() {
+            Key::from(format!("{}{}", prefix, encode_index_value(lo)))
+        } else {
+            Key::from(format!("{}{}\0", prefix, cursor))
+        };
+        // The `:` before the suffix matters: without it, a value that extends `hi`
+        // as a string (e.g. "abcd" when hi is "abc") would wrongly be included.
+        let end_key: Key = Key::from(format!("{}{}:\u{10FFFF}", prefix, encode_index_value(hi)));
+        let range: BoundRange = (start_key..end_key).into();
+
+        self.hydrate_index_scan(collection, &prefix, range, limit, |k, _prefix| {
+            k.rsplit(':').next().unwrap_or(k).to_string()
+        }).await
+    }
+
+    /// Shared `query`/`query_range` body: fetches `limit + 1` index entries to detect
+    /// more pages, hydrates each matching doc id via `get`, and reports a `ScanResult`
+    /// the same way `scan_range` does. The cursor is the last index key's part after
+    /// `prefix`: the doc id for `query`, `<encoded_value>:<docid>` for `query_range`.
+    async fn hydrate_index_scan(
+        &mut self,
+        collection: &str,
+        prefix: &str,
+        range: BoundRange,
+        limit: u32,
+        doc_id_of: impl Fn(&str, &str) -> String,
+    ) -> Result<ScanResult, OsunbitDBError> {
+        let fetch_limit = limit.saturating_add(1);
+        let mut kvs: Vec<KvPair> = self.tx.scan(range, fetch_limit).await?.collect();
+
+        let has_more = kvs.len() as u32 > limit;
+        if has_more {
+            kvs.truncate(limit as usize);
+        }
+
+        let mut items = serde_json::Map::new();
+        let mut last_id = None;
+        for kv in kvs {
+            let k = String::from_utf8_lossy(kv.key().as_ref().into()).to_string();
+            let doc_id = doc_id_of(&k, prefix);
+            if let Some(doc) = self.get(collection, &doc_id).await? {
+                items.insert(doc_id, doc);
+            }
+            last_id = Some(k.strip_prefix(prefix).unwrap_or(&k).to_string());
+        }
+
+        let next_cursor = if has_more { last_id } else { None };
+
+        Ok(ScanResult { items, next_cursor, has_more })
+    }
+
 
 pub async fn update(
     &mut self,
@@ -44,78 +338,103 @@ pub async fn update(
     fields: &JsonValue,
 ) -> Result<(), OsunbitDBError> {
     let mut data = self.get(collection, id).await?.unwrap_or(JsonValue::Object(Map::new()));
+    let old_expiry = expiry_of(&data);
 
     if let JsonValue::Object(ref mut obj) = data {
-        if let JsonValue::Object(new_fields) = fields {
-            for (k, v) in new_fields {
-                if let Some(op) = v.get("__op") {
-                    match op.as_str().unwrap_or("") {
-                        "inc" => {
-                            let delta = v["amount"].as_i64().unwrap_or(0);
-                            let mut current_val = 0;
-                            if let Some(existing) = get_deep(obj, k).and_then(|val| val.as_i64()) {
-                                current_val = existing;
-                            }
-                            set_deep(obj, k, json!(current_val + delta));
-                        }
-                        "remove" => {
-                            remove_deep(obj, k);
-                        }
-                        "array_union" => {
-                            let new_vals = v["values"].as_array().cloned().unwrap_or_default();
-                            let mut existing = get_deep(obj, k)
-                                .and_then(|val| val.as_array().cloned())
-                                .unwrap_or_default();
-
-                            for nv in new_vals {
-                                if !existing.contains(&nv) {
-                                    existing.push(nv);
-                                }
-                            }
+        apply_op_fields(obj, fields)?;
+        bump_version(obj);
+    }
 
-                            set_deep(obj, k, JsonValue::Array(existing));
-                        }
-                        "array_remove" => {
-                            let rem_vals = v["values"].as_array().cloned().unwrap_or_default();
-                            let mut existing = get_deep(obj, k)
-                                .and_then(|val| val.as_array().cloned())
-                                .unwrap_or_default();
+    // Persist the updated document
+    self.add(collection, id, &data).await?;
+    self.write_ttl_marker(collection, id, old_expiry.as_deref(), &data).await?;
 
-                            existing.retain(|item| !rem_vals.contains(item));
+    Ok(())
+}
 
-                            set_deep(obj, k, JsonValue::Array(existing));
-                        }
-                        _ => {
-                            set_deep(obj, k, v.clone());
-                        }
-                    }
-                } else {
-                    set_deep(obj, k, v.clone());
-                }
-            }
-        } else {
-            return Err(OsunbitDBError::InvalidUpdate(
-                "update fields must be an object".to_string(),
-            ));
-        }
+/// Re-reads `id`, rejects with `ConflictToken` if its `__version` no longer
+/// matches `expected_token`, otherwise applies the `__op` merge and bumps the version.
+pub async fn update_if(
+    &mut self,
+    collection: &str,
+    id: &str,
+    fields: &JsonValue,
+    expected_token: &str,
+) -> Result<(), OsunbitDBError> {
+    let mut data = self.get(collection, id).await?.unwrap_or(JsonValue::Object(Map::new()));
+    let current_token = version_token(&data);
+
+    if current_token != expected_token {
+        return Err(OsunbitDBError::ConflictToken(format!(
+            "expected version {}, found {}",
+            expected_token, current_token
+        )));
+    }
+
+    let old_expiry = expiry_of(&data);
+
+    if let JsonValue::Object(ref mut obj) = data {
+        apply_op_fields(obj, fields)?;
+        bump_version(obj);
     }
 
-    // Persist the updated document
     self.add(collection, id, &data).await?;
+    self.write_ttl_marker(collection, id, old_expiry.as_deref(), &data).await?;
+
+    Ok(())
+}
+
+/// The document's current `expiryAt` string, if set — used to find the stale
+/// `expire:` marker a write needs to clean up.
+fn expiry_of(data: &JsonValue) -> Option<String> {
+    data.as_object()
+        .and_then(|obj| get_deep(obj, "expiryAt"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+}
+
+/// Fetch a document alongside its causality token, for `update_if`.
+pub async fn get_with_token(
+    &mut self,
+    collection: &str,
+    id: &str,
+) -> Result<Option<(JsonValue, String)>, OsunbitDBError> {
+    match self.get(collection, id).await? {
+        Some(doc) => {
+            let token = version_token(&doc);
+            Ok(Some((doc, token)))
+        }
+        None => Ok(None),
+    }
+}
 
-    // ===== TTL handling: look for expiryAt directly (string value)
-    if let Some(expiry_val) = get_deep(
-        data.as_object().expect("doc must be object"),
-        "expiryAt",
-    ) {
-        if let Some(expiry_str) = expiry_val.as_str() {
-            // key format: expire:<expiryAt>:<docid>
-            let expire_key = Key::from(format!("expire:{}:{}", expiry_str, id));
-            let expire_val = Value::from(id.as_bytes().to_vec());
-            self.tx.put(expire_key, expire_val).await?;
+/// Keeps the `expire:` marker for `id` in sync with its `expiryAt`: removes
+/// `old_expiry`'s marker when the value changed or disappeared (otherwise a
+/// sweep that later passes the stale timestamp would delete a document whose
+/// current expiry is later, or that isn't expiring at all), then writes the
+/// marker for the document's new `expiryAt`, if any.
+async fn write_ttl_marker(
+    &mut self,
+    collection: &str,
+    id: &str,
+    old_expiry: Option<&str>,
+    data: &JsonValue,
+) -> Result<(), OsunbitDBError> {
+    let new_expiry = expiry_of(data);
+
+    if old_expiry != new_expiry.as_deref() {
+        if let Some(old_expiry_str) = old_expiry {
+            self.tx.delete(Key::from(format!("expire:{}:{}", old_expiry_str, id))).await?;
         }
     }
 
+    if let Some(expiry_str) = new_expiry {
+        // key format: expire:<expiryAt>:<docid>, value: <collection>:<docid>
+        // so a sweep can recover where the expired document actually lives.
+        let expire_key = Key::from(format!("expire:{}:{}", expiry_str, id));
+        let expire_val = Value::from(format!("{}:{}", collection, id).into_bytes());
+        self.tx.put(expire_key, expire_val).await?;
+    }
     Ok(())
 }
 
@@ -128,57 +447,58 @@ pub async fn update(
         self.tx.rollback().await?;
         Ok(())
     }
-pub async fn scan(
+/// Scan a sub-range of `collection`'s doc ids in either direction, with
+/// opaque-cursor pagination. `opts.start`/`opts.end` bound the scan to doc
+/// ids within the collection (e.g. `"2024-01"..="2024-02"`); leaving one
+/// unset scans to that edge of the collection.
+pub async fn scan_range(
     &mut self,
     collection: &str,
-    limit: u32,
-    cursor: &str,
-) -> Result<JsonValue, OsunbitDBError> {
+    opts: &ScanOptions,
+) -> Result<ScanResult, OsunbitDBError> {
+    // A doc id has no delimiter after it in this key, so excluding/including it
+    // exactly needs its true successor (id + 0x00), not a string-max suffix —
+    // that would wrongly include/exclude sibling ids that extend it (e.g. "u1" vs "u10").
+    let start_key_str = match (&opts.start, opts.start_exclusive) {
+        (Some(s), true) => format!("{}:{}\0", collection, s),
+        (Some(s), false) => format!("{}:{}", collection, s),
+        (None, _) => format!("{}:", collection),
+    };
+    let end_key_str = match (&opts.end, opts.end_exclusive) {
+        (Some(e), true) => format!("{}:{}", collection, e),
+        (Some(e), false) => format!("{}:{}\0", collection, e),
+        (None, _) => format!("{}:\u{10FFFF}", collection),
+    };
+
+    let range: BoundRange = (Key::from(start_key_str)..Key::from(end_key_str)).into();
     let prefix = format!("{}:", collection);
 
-    // If no cursor: start from very end of collection
-    let start_key: Key = if cursor.is_empty() {
-        Key::from(format!("{}:\u{10FFFF}", collection))
+    // Fetch one extra so we can tell whether more pages remain.
+    let fetch_limit = opts.limit.saturating_add(1);
+    let mut kvs: Vec<KvPair> = if opts.reverse {
+        self.tx.scan_reverse(range, fetch_limit).await?.collect()
     } else {
-        Key::from(format!("{}:{}\u{10FFFF}", collection, cursor))
+        self.tx.scan(range, fetch_limit).await?.collect()
     };
 
-    // Lowest possible key for this collection
-    let end_key: Key = Key::from(format!("{}:", collection));
-
-    // Range covers full collection space
-    let range: BoundRange = (end_key..=start_key).into();
-
-    // Fetch limit + 1 so we can safely drop the cursor
-    let kvs: Vec<KvPair> = self
-        .tx
-        .scan_reverse(range, (limit + 1) as u32)
-        .await?
-        .collect();
-
-    let mut out = serde_json::Map::new();
-    let mut count = 0;
+    let has_more = kvs.len() as u32 > opts.limit;
+    if has_more {
+        kvs.truncate(opts.limit as usize);
+    }
 
+    let mut items = serde_json::Map::new();
+    let mut last_id = None;
     for kv in kvs {
-        let k_bytes = kv.key().as_ref();
-        let k = String::from_utf8_lossy(k_bytes.into()).to_string();
+        let k = String::from_utf8_lossy(kv.key().as_ref().into()).to_string();
         let doc_id = k.strip_prefix(&prefix).unwrap_or(&k).to_string();
-
-        // Skip the cursor itself
-        if !cursor.is_empty() && doc_id == cursor {
-            continue;
-        }
-
         let v = serde_json::from_slice(&kv.value().to_vec()).unwrap_or(JsonValue::Null);
-        out.insert(doc_id, v);
-
-        count += 1;
-        if count == limit {
-            break;
-        }
+        last_id = Some(doc_id.clone());
+        items.insert(doc_id, v);
     }
 
-    Ok(JsonValue::Object(out))
+    let next_cursor = if has_more { last_id } else { None };
+
+    Ok(ScanResult { items, next_cursor, has_more })
 }
 
 pub async fn batch_add(&mut self, collection: &str, items_json: &JsonValue) -> Result<(), OsunbitDBError> {
@@ -221,4 +541,77 @@ pub async fn batch_add(&mut self, collection: &str, items_json: &JsonValue) -> R
         Ok(())
     }
 
+    /// Apply an ordered list of heterogeneous writes — inserts, updates, and
+    /// deletes across any mix of collections — inside this transaction, the
+    /// way MongoDB's `bulkWrite` does. `UpdateOne` does not upsert: it's a
+    /// no-op (not matched, not modified) when the id doesn't exist. Likewise
+    /// `DeleteOne` only counts `deleted` for ids that actually existed.
+    pub async fn bulk_write(&mut self, models: Vec<WriteModel>) -> Result<BulkWriteResult, OsunbitDBError> {
+        let mut result = BulkWriteResult::default();
+        for model in models {
+            match model {
+                WriteModel::InsertOne { collection, id, document } => {
+                    self.add(&collection, &id, &document).await?;
+                    result.inserted += 1;
+                }
+                WriteModel::UpdateOne { collection, id, fields } => {
+                    if self.get(&collection, &id).await?.is_some() {
+                        self.update(&collection, &id, &fields).await?;
+                        result.matched += 1;
+                        result.modified += 1;
+                    }
+                }
+                WriteModel::DeleteOne { collection, id } => {
+                    if self.get(&collection, &id).await?.is_some() {
+                        self.delete(&collection, &id).await?;
+                        result.deleted += 1;
+                    }
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Reap documents whose `expire:<expiryAt>:<docid>` marker is already due, up to
+    /// `limit` per call; pass the returned cursor back in to keep sweeping.
+    pub async fn sweep_expired(
+        &mut self,
+        now: &str,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<(u32, Option<String>), OsunbitDBError> {
+        let start_key: Key = match cursor {
+            Some(c) => Key::from(c.to_string()),
+            None => Key::from("expire:".to_string()),
+        };
+        let end_key: Key = Key::from(format!("expire:{}\u{10FFFF}", now));
+        let range: BoundRange = (start_key..end_key).into();
+
+        // Fetch one extra so we can tell whether more due markers remain.
+        let kvs: Vec<KvPair> = self.tx.scan(range, limit.saturating_add(1)).await?.collect();
+
+        let mut reaped = 0u32;
+        let mut next_cursor = None;
+
+        for (i, kv) in kvs.into_iter().enumerate() {
+            if i as u32 == limit {
+                let k_bytes = kv.key().as_ref();
+                next_cursor = Some(String::from_utf8_lossy(k_bytes.into()).to_string());
+                break;
+            }
+
+            let marker_key = kv.key().clone();
+            let marker = String::from_utf8_lossy(&kv.value().to_vec()).to_string();
+            if let Some((collection, doc_id)) = marker.split_once(':') {
+                // Route through `delete` (not a raw `tx.delete`) so index entries
+                // for this document are cleaned up too, same as any other delete.
+                self.delete(collection, doc_id).await?;
+            }
+            self.tx.delete(marker_key).await?;
+            reaped += 1;
+        }
+
+        Ok((reaped, next_cursor))
+    }
+
 }
@@ -11,6 +11,40 @@ pub fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, OsunbitDBError> {
     Ok(bincode::deserialize(bytes)?)
 }
 
+pub fn make_key(collection: &str, id: &str) -> Vec<u8> {
+    format!("{}:{}", collection, id).into_bytes()
+}
+
+/// Encode a JSON scalar so that byte-lexicographic order on the result
+/// matches the value's natural order: numbers are always promoted through
+/// `f64` and bit-ordered, so an int field and a float field with the same
+/// value (e.g. `10` and `10.0`, which `mul`/`min`/`max` can turn one into
+/// the other) land on the same index entry; strings are used as-is.
+pub fn encode_index_value(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(s) => s.clone(),
+        JsonValue::Number(n) => {
+            if let Some(f) = n.as_f64() {
+                format!("{:020}", encode_f64_ordered(f))
+            } else {
+                n.to_string()
+            }
+        }
+        JsonValue::Bool(b) => b.to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Map an `f64`'s bit pattern to a `u64` whose unsigned order matches the float's order.
+fn encode_f64_ordered(f: f64) -> u64 {
+    let bits = f.to_bits();
+    if bits & (1 << 63) != 0 {
+        !bits
+    } else {
+        bits | (1 << 63)
+    }
+}
+
 /// Dot-notation: set deeply
 pub fn set_deep(obj: &mut Map<String, JsonValue>, path: &str, value: JsonValue) {
     let mut parts = path.split('.').peekable();
@@ -0,0 +1,48 @@
+use osunbitdb::{OsunbitDB, json, WriteModel};
+
+#[tokio::test]
+async fn bulk_write_test() {
+    let db = OsunbitDB::new(&["http://10.88.0.3:2379"]).await.unwrap();
+
+    db.add("accounts", "a1", &json!({ "balance": 100 })).await.unwrap();
+
+    let result = db.bulk_write(vec![
+        WriteModel::InsertOne {
+            collection: "accounts".to_string(),
+            id: "a2".to_string(),
+            document: json!({ "balance": 0 }),
+        },
+        WriteModel::UpdateOne {
+            collection: "accounts".to_string(),
+            id: "a1".to_string(),
+            fields: json!({ "balance": 50 }),
+        },
+        WriteModel::UpdateOne {
+            collection: "accounts".to_string(),
+            id: "missing".to_string(),
+            fields: json!({ "balance": 999 }),
+        },
+        WriteModel::DeleteOne {
+            collection: "accounts".to_string(),
+            id: "a2".to_string(),
+        },
+        // DeleteOne on an id that doesn't exist must not count towards `deleted`.
+        WriteModel::DeleteOne {
+            collection: "accounts".to_string(),
+            id: "missing".to_string(),
+        },
+    ]).await.unwrap();
+
+    assert_eq!(result.inserted, 1);
+    assert_eq!(result.matched, 1);
+    assert_eq!(result.modified, 1);
+    assert_eq!(result.deleted, 1);
+
+    // UpdateOne must not upsert a document that didn't exist
+    assert!(db.get("accounts", "missing").await.unwrap().is_none());
+
+    let a1 = db.get("accounts", "a1").await.unwrap().unwrap();
+    assert_eq!(a1["balance"], 50);
+
+    db.delete("accounts", "a1").await.unwrap();
+}
@@ -0,0 +1,89 @@
+use osunbitdb::{OsunbitDB, json, multiply};
+
+#[tokio::test]
+async fn query_and_query_range_test() {
+    let db = OsunbitDB::new(&["http://10.88.0.3:2379"]).await.unwrap();
+
+    db.create_index("players", "level").await.unwrap();
+
+    db.add("players", "p1", &json!({ "level": 5 })).await.unwrap();
+    db.add("players", "p2", &json!({ "level": 10 })).await.unwrap();
+    db.add("players", "p3", &json!({ "level": 10 })).await.unwrap();
+    db.add("players", "p4", &json!({ "level": 20 })).await.unwrap();
+
+    let exact = db.query("players", "level", &json!(10), 10, "").await.unwrap();
+    assert_eq!(exact.items.len(), 2);
+    assert!(exact.items.contains_key("p2"));
+    assert!(exact.items.contains_key("p3"));
+    assert!(!exact.has_more);
+
+    let ranged = db.query_range("players", "level", &json!(5), &json!(10), 10, "").await.unwrap();
+    assert_eq!(ranged.items.len(), 3);
+    assert!(!ranged.items.contains_key("p4"));
+    assert!(!ranged.has_more);
+
+    // Moving a value to another one must update idx: entries too
+    db.update("players", "p1", &json!({ "level": 999 })).await.unwrap();
+    let after_move = db.query("players", "level", &json!(5), 10, "").await.unwrap();
+    assert!(after_move.items.is_empty());
+
+    for id in ["p1", "p2", "p3", "p4"] {
+        db.delete("players", id).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn query_matches_across_int_and_float_test() {
+    let db = OsunbitDB::new(&["http://10.88.0.3:2379"]).await.unwrap();
+
+    db.create_index("scores", "value").await.unwrap();
+
+    // `score: 10` turned into a float by `multiply` must still be found by
+    // a query for `10` (and vice versa) — int and float of equal value must
+    // encode to the same index entry.
+    db.add("scores", "s1", &json!({ "value": 10 })).await.unwrap();
+    db.update("scores", "s1", &json!({ "value": multiply(1.0).unwrap() })).await.unwrap();
+    assert_eq!(db.get("scores", "s1").await.unwrap().unwrap()["value"], 10.0);
+
+    let by_int = db.query("scores", "value", &json!(10), 10, "").await.unwrap();
+    assert!(by_int.items.contains_key("s1"));
+
+    let by_float = db.query("scores", "value", &json!(10.0), 10, "").await.unwrap();
+    assert!(by_float.items.contains_key("s1"));
+
+    db.delete("scores", "s1").await.unwrap();
+}
+
+#[tokio::test]
+async fn query_and_query_range_paginate_test() {
+    let db = OsunbitDB::new(&["http://10.88.0.3:2379"]).await.unwrap();
+
+    db.create_index("players", "guild").await.unwrap();
+
+    for id in ["g1", "g2", "g3"] {
+        db.add("players", id, &json!({ "guild": "alpha" })).await.unwrap();
+    }
+
+    let page1 = db.query("players", "guild", &json!("alpha"), 2, "").await.unwrap();
+    assert_eq!(page1.items.len(), 2);
+    assert!(page1.has_more);
+    let cursor = page1.next_cursor.unwrap();
+
+    let page2 = db.query("players", "guild", &json!("alpha"), 2, &cursor).await.unwrap();
+    assert_eq!(page2.items.len(), 1);
+    assert!(!page2.has_more);
+    assert_eq!(page1.items.len() + page2.items.len(), 3);
+
+    let range_page1 = db.query_range("players", "guild", &json!("alpha"), &json!("alpha"), 2, "").await.unwrap();
+    assert_eq!(range_page1.items.len(), 2);
+    assert!(range_page1.has_more);
+    let range_cursor = range_page1.next_cursor.unwrap();
+
+    let range_page2 = db.query_range("players", "guild", &json!("alpha"), &json!("alpha"), 2, &range_cursor).await.unwrap();
+    assert_eq!(range_page2.items.len(), 1);
+    assert!(!range_page2.has_more);
+
+    for id in ["g1", "g2", "g3"] {
+        db.delete("players", id).await.unwrap();
+    }
+}
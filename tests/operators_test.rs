@@ -0,0 +1,40 @@
+use osunbitdb::{OsunbitDB, json, array_remove, maximum, minimum, multiply, set_on_insert};
+
+#[tokio::test]
+async fn field_operators_test() {
+    let db = OsunbitDB::new(&["http://10.88.0.3:2379"]).await.unwrap();
+
+    db.add("metrics", "m1", &json!({
+        "score": 10,
+        "temperature": 98.6,
+        "tags": ["a", "b", "c"],
+    })).await.unwrap();
+
+    db.update("metrics", "m1", &json!({
+        "score": multiply(1.5).unwrap(),
+        "temperature": minimum(97.0).unwrap(),
+        "tags": array_remove(json!(["b"])),
+        "createdAt": set_on_insert(json!("2024-01-01")),
+    })).await.unwrap();
+
+    let doc = db.get("metrics", "m1").await.unwrap().unwrap();
+    assert_eq!(doc["score"], 15.0);
+    assert_eq!(doc["temperature"], 97.0);
+    assert_eq!(doc["tags"], json!(["a", "c"]));
+    assert_eq!(doc["createdAt"], "2024-01-01");
+
+    // set_on_insert must not clobber an existing value
+    db.update("metrics", "m1", &json!({
+        "createdAt": set_on_insert(json!("2099-01-01")),
+        "score": maximum(100.0).unwrap(),
+    })).await.unwrap();
+
+    let doc = db.get("metrics", "m1").await.unwrap().unwrap();
+    assert_eq!(doc["createdAt"], "2024-01-01");
+    assert_eq!(doc["score"], 100.0);
+
+    assert!(multiply(f64::NAN).is_err());
+    assert!(minimum(f64::INFINITY).is_err());
+
+    db.delete("metrics", "m1").await.unwrap();
+}
@@ -0,0 +1,72 @@
+use osunbitdb::{OsunbitDB, json, ScanOptions};
+
+#[tokio::test]
+async fn scan_range_test() {
+    let db = OsunbitDB::new(&["http://10.88.0.3:2379"]).await.unwrap();
+
+    for id in ["u1", "u10", "u11", "u2"] {
+        db.add("users", id, &json!({ "id": id })).await.unwrap();
+    }
+
+    // Exclusive start of "u1" must not skip "u10"/"u11", which extend "u1" as a string.
+    let page = db.scan_range("users", &ScanOptions {
+        start: Some("u1".to_string()),
+        start_exclusive: true,
+        limit: 10,
+        ..Default::default()
+    }).await.unwrap();
+    assert_eq!(page.items.len(), 3);
+    assert!(page.items.contains_key("u10"));
+    assert!(page.items.contains_key("u11"));
+    assert!(page.items.contains_key("u2"));
+    assert!(!page.items.contains_key("u1"));
+
+    // Inclusive end of "u1" must include exactly "u1" but not "u10"/"u11".
+    let page = db.scan_range("users", &ScanOptions {
+        end: Some("u1".to_string()),
+        limit: 10,
+        ..Default::default()
+    }).await.unwrap();
+    assert_eq!(page.items.len(), 1);
+    assert!(page.items.contains_key("u1"));
+
+    // Pagination cursor
+    let page1 = db.scan_range("users", &ScanOptions { limit: 2, ..Default::default() }).await.unwrap();
+    assert_eq!(page1.items.len(), 2);
+    assert!(page1.has_more);
+    let cursor = page1.next_cursor.unwrap();
+
+    let page2 = db.scan_range("users", &ScanOptions {
+        start: Some(cursor),
+        start_exclusive: true,
+        limit: 10,
+        ..Default::default()
+    }).await.unwrap();
+    assert_eq!(page1.items.len() + page2.items.len(), 4);
+
+    // Pagination cursor, reverse direction: the cursor feeds into an exclusive `end`,
+    // not an exclusive `start` as it does when scanning forward.
+    let page1 = db.scan_range("users", &ScanOptions { reverse: true, limit: 2, ..Default::default() }).await.unwrap();
+    assert_eq!(page1.items.len(), 2);
+    assert!(page1.items.contains_key("u2"));
+    assert!(page1.items.contains_key("u11"));
+    assert!(page1.has_more);
+    let cursor = page1.next_cursor.unwrap();
+    assert_eq!(cursor, "u11");
+
+    let page2 = db.scan_range("users", &ScanOptions {
+        end: Some(cursor),
+        end_exclusive: true,
+        reverse: true,
+        limit: 10,
+        ..Default::default()
+    }).await.unwrap();
+    assert_eq!(page2.items.len(), 2);
+    assert!(page2.items.contains_key("u10"));
+    assert!(page2.items.contains_key("u1"));
+    assert!(!page2.has_more);
+
+    for id in ["u1", "u10", "u11", "u2"] {
+        db.delete("users", id).await.unwrap();
+    }
+}
@@ -0,0 +1,73 @@
+use osunbitdb::{OsunbitDB, json, remove};
+
+#[tokio::test]
+async fn sweep_expired_test() {
+    let db = OsunbitDB::new(&["http://10.88.0.3:2379"]).await.unwrap();
+
+    db.update("sessions", "s1", &json!({ "expiryAt": "2020-01-01T00:00:00Z" })).await.unwrap();
+    db.update("sessions", "s2", &json!({ "expiryAt": "2020-01-02T00:00:00Z" })).await.unwrap();
+    db.update("sessions", "s3", &json!({ "expiryAt": "2099-01-01T00:00:00Z" })).await.unwrap();
+
+    let (reaped, cursor) = db.sweep_expired("2020-06-01T00:00:00Z", 10, None).await.unwrap();
+    assert_eq!(reaped, 2);
+    assert!(cursor.is_none());
+
+    assert!(db.get("sessions", "s1").await.unwrap().is_none());
+    assert!(db.get("sessions", "s2").await.unwrap().is_none());
+    assert!(db.get("sessions", "s3").await.unwrap().is_some());
+
+    db.delete("sessions", "s3").await.unwrap();
+}
+
+#[tokio::test]
+async fn sweep_expired_paginates_test() {
+    let db = OsunbitDB::new(&["http://10.88.0.3:2379"]).await.unwrap();
+
+    for i in 0..3 {
+        db.update("sessions", &format!("p{}", i), &json!({ "expiryAt": "2020-01-01T00:00:00Z" })).await.unwrap();
+    }
+
+    let (reaped, cursor) = db.sweep_expired("2020-06-01T00:00:00Z", 1, None).await.unwrap();
+    assert_eq!(reaped, 1);
+    assert!(cursor.is_some());
+
+    let (reaped, cursor) = db.sweep_expired("2020-06-01T00:00:00Z", 10, cursor.as_deref()).await.unwrap();
+    assert_eq!(reaped, 2);
+    assert!(cursor.is_none());
+}
+
+#[tokio::test]
+async fn update_expiry_clears_stale_marker_test() {
+    let db = OsunbitDB::new(&["http://10.88.0.3:2379"]).await.unwrap();
+
+    db.update("sessions", "s4", &json!({ "expiryAt": "2020-01-01T00:00:00Z" })).await.unwrap();
+
+    // Extending the expiry must drop the stale 2020 marker, not just add a 2099 one.
+    db.update("sessions", "s4", &json!({ "expiryAt": "2099-01-01T00:00:00Z" })).await.unwrap();
+    let (reaped, _) = db.sweep_expired("2020-06-01T00:00:00Z", 10, None).await.unwrap();
+    assert_eq!(reaped, 0);
+    assert!(db.get("sessions", "s4").await.unwrap().is_some());
+
+    // Removing expiryAt entirely must also drop the marker.
+    db.update("sessions", "s4", &json!({ "expiryAt": remove() })).await.unwrap();
+    let (reaped, _) = db.sweep_expired("2099-06-01T00:00:00Z", 10, None).await.unwrap();
+    assert_eq!(reaped, 0);
+    assert!(db.get("sessions", "s4").await.unwrap().is_some());
+
+    db.delete("sessions", "s4").await.unwrap();
+}
+
+#[tokio::test]
+async fn sweep_expired_cleans_up_index_test() {
+    let db = OsunbitDB::new(&["http://10.88.0.3:2379"]).await.unwrap();
+
+    db.create_index("sessions", "userId").await.unwrap();
+    db.update("sessions", "s5", &json!({ "userId": "u1", "expiryAt": "2020-01-01T00:00:00Z" })).await.unwrap();
+
+    let (reaped, _) = db.sweep_expired("2020-06-01T00:00:00Z", 10, None).await.unwrap();
+    assert_eq!(reaped, 1);
+
+    // The reaper must route through `delete` so the idx: entry doesn't dangle.
+    let matches = db.query("sessions", "userId", &json!("u1"), 10, "").await.unwrap();
+    assert!(matches.items.is_empty());
+}
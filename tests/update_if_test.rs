@@ -0,0 +1,50 @@
+use osunbitdb::{OsunbitDB, OsunbitDBError, json, increment};
+
+#[tokio::test]
+async fn update_if_test() {
+    let db = OsunbitDB::new(&["http://10.88.0.3:2379"]).await.unwrap();
+
+    db.add("accounts", "a1", &json!({ "balance": 100 })).await.unwrap();
+
+    let (doc, token) = db.get_with_token("accounts", "a1").await.unwrap().unwrap();
+    assert_eq!(doc["balance"], 100);
+    assert_eq!(token, "0");
+
+    db.update_if("accounts", "a1", &json!({ "balance": increment(50) }), &token).await.unwrap();
+
+    let (doc, token) = db.get_with_token("accounts", "a1").await.unwrap().unwrap();
+    assert_eq!(doc["balance"], 150);
+    assert_eq!(token, "1");
+
+    // A stale token must be rejected with ConflictToken
+    let err = db.update_if("accounts", "a1", &json!({ "balance": increment(50) }), "0").await;
+    assert!(matches!(err, Err(OsunbitDBError::ConflictToken(_))));
+
+    // The current token still applies cleanly
+    db.update_if("accounts", "a1", &json!({ "balance": increment(50) }), &token).await.unwrap();
+    let final_doc = db.get("accounts", "a1").await.unwrap().unwrap();
+    assert_eq!(final_doc["balance"], 200);
+
+    db.delete("accounts", "a1").await.unwrap();
+}
+
+#[tokio::test]
+async fn plain_update_invalidates_token_test() {
+    let db = OsunbitDB::new(&["http://10.88.0.3:2379"]).await.unwrap();
+
+    db.add("accounts", "a2", &json!({ "balance": 100 })).await.unwrap();
+
+    let (_, token) = db.get_with_token("accounts", "a2").await.unwrap().unwrap();
+
+    // A concurrent plain `update` (e.g. via bulk_write) must bump __version too,
+    // so the token taken before it is no longer valid for update_if.
+    db.update("accounts", "a2", &json!({ "balance": increment(10) })).await.unwrap();
+
+    let err = db.update_if("accounts", "a2", &json!({ "balance": increment(50) }), &token).await;
+    assert!(err.is_err());
+
+    let final_doc = db.get("accounts", "a2").await.unwrap().unwrap();
+    assert_eq!(final_doc["balance"], 110);
+
+    db.delete("accounts", "a2").await.unwrap();
+}